@@ -7,14 +7,40 @@
 *   3. Any live cell with more than three live neighbors dies, as if by overpopulation.
 *   4. Any dead cell with exactly three live neighbors becomes a live cell, as if by reproduction.
 */
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
 use termion::*;
-const WINDOW_WIDTH: u16 = 130;
-const WINDOW_HEIGHT: u16 = 40;
 const FRAME_DURATION_MS: u128 = 220;
 const ALIVE_SYMBOL: char = '█';
 const DEAD_SYMBOL: char = ' ';
+/// How many prior generations the sparse backend keeps for step-back.
+const HISTORY_CAPACITY: usize = 256;
 
+/// The board dimensions, derived from the terminal at startup and refreshed
+/// whenever the window is resized so the simulation fills whatever space is
+/// available.
 #[derive(Clone, Copy, PartialEq, Eq)]
+struct Dimensions {
+    width: u16,
+    height: u16,
+}
+
+impl Dimensions {
+    /// Reads the current terminal size, clamping each axis to a 1-cell
+    /// minimum so a terminal that reports a zero dimension still yields a
+    /// usable board instead of an empty one that later indexing panics on.
+    fn from_terminal() -> Dimensions {
+        let (width, height) = termion::terminal_size().unwrap();
+        Dimensions {
+            width: width.max(1),
+            height: height.max(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CellState {
     Alive,
     Dead,
@@ -25,60 +51,685 @@ struct Cell {
     x: u16,
     y: u16,
     state: CellState,
+    /// Number of generations this cell has been continuously alive; reset
+    /// to zero on birth and incremented on each survival.
+    age: u32,
+}
+
+impl Cell {
+    /// Flips the cell between alive and dead, resetting its age — used when
+    /// hand-painting the board in the interactive editor.
+    fn toggle(&mut self) {
+        self.state = match self.state {
+            CellState::Alive => CellState::Dead,
+            CellState::Dead => CellState::Alive,
+        };
+        self.age = 0;
+    }
+}
+
+/// How the grid edges behave. `Bounded` treats them as hard walls;
+/// `Toroidal` wraps them so a pattern leaving one edge re-enters on the
+/// opposite side.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BoundaryMode {
+    Bounded,
+    Toroidal,
+}
+
+/// A Life-like rule expressed as the sets of neighbor counts that cause a
+/// dead cell to be born and a live cell to survive, as in the standard
+/// `B3/S23` (Conway) notation.
+struct Rule {
+    born: HashSet<i32>,
+    survives: HashSet<i32>,
+}
+
+impl Rule {
+    /// Conway's Life: `B3/S23`.
+    fn conway() -> Rule {
+        Rule::parse("B3/S23").unwrap()
+    }
+
+    /// Parses a `B<counts>/S<counts>` rule string; either side may be empty
+    /// (e.g. `B2/S` for Seeds).
+    fn parse(s: &str) -> Result<Rule, String> {
+        let mut born = HashSet::new();
+        let mut survives = HashSet::new();
+        let mut parts = s.split('/');
+        let b = parts.next().unwrap_or("");
+        let surv = parts.next().unwrap_or("");
+        for (prefix, section, set) in [('B', b, &mut born), ('S', surv, &mut survives)] {
+            let digits = section
+                .strip_prefix(prefix)
+                .or_else(|| section.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(|| format!("expected '{}' prefix in '{}'", prefix, section))?;
+            for ch in digits.chars() {
+                let n = ch
+                    .to_digit(10)
+                    .ok_or_else(|| format!("invalid digit '{}' in rule", ch))?;
+                set.insert(n as i32);
+            }
+        }
+        Ok(Rule { born, survives })
+    }
+}
+
+/// Which board representation to run. The dense grid is the classic
+/// default; the sparse backend stores only live coordinates and supports
+/// rewinding through its generation history.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BackendKind {
+    Dense,
+    Sparse,
+}
+
+/// A board representation that can advance one generation and report the
+/// coordinates of its live cells. This is the seam the sparse backend
+/// plugs into alongside the classic dense grid.
+///
+/// The trait deliberately stays this minimal: it has no notion of per-cell
+/// age or of hand-painting individual cells, so the cursor-painting editor
+/// and the age-color gradient are dense-grid-only features (see
+/// `run_dense`). `--backend sparse` is a rewindable simulator, not a drop-in
+/// replacement for the dense editor.
+trait Backend {
+    fn tick(&mut self, boundary: BoundaryMode, rule: &Rule, dims: Dimensions);
+    fn live_cells(&self, dims: Dimensions) -> Vec<(u16, u16)>;
+}
+
+/// A sparse board storing only the coordinates of live cells, with a
+/// bounded ring buffer of prior generations for step-back and the saved
+/// initial state for reset.
+struct SparseBoard {
+    live: HashSet<(isize, isize)>,
+    history: VecDeque<HashSet<(isize, isize)>>,
+    initial: HashSet<(isize, isize)>,
+}
+
+impl SparseBoard {
+    fn new(live: HashSet<(isize, isize)>) -> SparseBoard {
+        SparseBoard {
+            initial: live.clone(),
+            live,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Rewinds to the previous generation, if one is still buffered.
+    fn step_back(&mut self) {
+        if let Some(prev) = self.history.pop_back() {
+            self.live = prev;
+        }
+    }
+
+    /// Restores the saved initial state and clears the history buffer.
+    fn reset(&mut self) {
+        self.live = self.initial.clone();
+        self.history.clear();
+    }
+}
+
+impl Backend for SparseBoard {
+    fn tick(&mut self, boundary: BoundaryMode, rule: &Rule, dims: Dimensions) {
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.live.clone());
+
+        // Tally how many live neighbors each candidate cell has by walking
+        // outward from every live cell. A live cell is seeded into the map
+        // with a zero count even if it has no live neighbors itself, so a
+        // rule with 0 in its survival set (e.g. B3/S012345678) still keeps
+        // it alive instead of the cell silently never being considered.
+        let mut counts: HashMap<(isize, isize), i32> = HashMap::new();
+        for &(x, y) in self.live.iter() {
+            counts.entry((x, y)).or_insert(0);
+            for neighbor in sparse_neighbors(x, y, boundary, dims) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let mut next = HashSet::new();
+        for (&cell, &n) in counts.iter() {
+            let alive = self.live.contains(&cell);
+            if (alive && rule.survives.contains(&n)) || (!alive && rule.born.contains(&n)) {
+                next.insert(cell);
+            }
+        }
+        self.live = next;
+    }
+
+    fn live_cells(&self, dims: Dimensions) -> Vec<(u16, u16)> {
+        self.live
+            .iter()
+            .filter(|&&(x, y)| {
+                x >= 0 && y >= 0 && x < dims.width as isize && y < dims.height as isize
+            })
+            .map(|&(x, y)| (x as u16, y as u16))
+            .collect()
+    }
+}
+
+/// The eight neighbor coordinates of `(x, y)`, honoring the boundary mode:
+/// out-of-range neighbors are dropped when bounded and wrapped when toroidal.
+fn sparse_neighbors(
+    x: isize,
+    y: isize,
+    boundary: BoundaryMode,
+    dims: Dimensions,
+) -> Vec<(isize, isize)> {
+    let (w, h) = (dims.width as isize, dims.height as isize);
+    let mut out = Vec::with_capacity(8);
+    for i in -1..=1 {
+        for j in -1..=1 {
+            if i == 0 && j == 0 {
+                continue;
+            }
+            match boundary {
+                BoundaryMode::Bounded => {
+                    let nx = x + i;
+                    let ny = y + j;
+                    if nx >= 0 && ny >= 0 && nx < w && ny < h {
+                        out.push((nx, ny));
+                    }
+                }
+                BoundaryMode::Toroidal => {
+                    out.push(((x + i + w) % w, (y + j + h) % h));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// A loaded starting pattern: its bounding-box dimensions plus the
+/// `(column, row)` coordinates of every live cell inside that box.
+struct Pattern {
+    width: usize,
+    height: usize,
+    alive: Vec<(usize, usize)>,
 }
 
 fn main() {
-    check_terminal_size();
+    let dims = Dimensions::from_terminal();
+    let pattern = match parse_pattern_arg() {
+        Some(path) => match load_pattern(&path, dims) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                eprintln!("Failed to load pattern '{}': {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let boundary = match parse_boundary_arg() {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("Failed to parse boundary: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let rule = match parse_rule_arg() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Failed to parse rule: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let backend = match parse_backend_arg() {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("Failed to parse backend: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let board = initialize_board(pattern.as_ref(), dims);
+
+    match backend {
+        BackendKind::Dense => run_dense(board, boundary, &rule, dims),
+        BackendKind::Sparse => {
+            let mut sparse = SparseBoard::new(live_set(&board));
+            run_sparse(&mut sparse, boundary, &rule, dims);
+        }
+    }
+}
+
+/// The dense run loop. It auto-advances on the frame timer, but polls for
+/// key events each frame instead of blindly sleeping: space pauses, and
+/// while paused the arrow keys move a cursor, `t` toggles the cell under it,
+/// `.` single-steps and `c` clears the board. `q` quits in either mode. The
+/// board is re-fitted whenever the terminal is resized.
+fn run_dense(mut board: Vec<Vec<Cell>>, boundary: BoundaryMode, rule: &Rule, mut dims: Dimensions) {
+    let _raw = std::io::stdout().into_raw_mode().unwrap();
+    let mut keys = termion::async_stdin().keys();
+    let mut paused = false;
+    let mut cursor = (dims.width / 2, dims.height / 2);
     let mut last_update = std::time::Instant::now();
-    let mut board = initialize_board();
 
     loop {
-        update_board(&mut board);
-        render_board(&board);
-        wait_for_next_frame(last_update);
-        last_update = std::time::Instant::now();
+        if handle_resize(&mut dims) {
+            board = refit_board(board, dims);
+            cursor = (
+                cursor.0.min(dims.width.saturating_sub(1)),
+                cursor.1.min(dims.height.saturating_sub(1)),
+            );
+            print!("{}", clear::All);
+        }
+
+        render_board(&board, dims);
+        if paused {
+            draw_cursor(cursor.0, cursor.1, dims);
+        }
+        std::io::stdout().flush().unwrap();
+
+        while let Some(Ok(key)) = keys.next() {
+            match key {
+                event::Key::Char('q') => return,
+                event::Key::Char(' ') => paused = !paused,
+                event::Key::Char('.') if paused => update_board(&mut board, boundary, rule, dims),
+                event::Key::Char('c') if paused => clear_board(&mut board),
+                event::Key::Char('t') if paused => {
+                    board[cursor.0 as usize][cursor.1 as usize].toggle()
+                }
+                event::Key::Left if paused => cursor.0 = cursor.0.saturating_sub(1),
+                event::Key::Right if paused => {
+                    cursor.0 = (cursor.0 + 1).min(dims.width.saturating_sub(1))
+                }
+                event::Key::Up if paused => cursor.1 = cursor.1.saturating_sub(1),
+                event::Key::Down if paused => {
+                    cursor.1 = (cursor.1 + 1).min(dims.height.saturating_sub(1))
+                }
+                _ => {}
+            }
+        }
+
+        if !paused && last_update.elapsed().as_millis() >= FRAME_DURATION_MS {
+            update_board(&mut board, boundary, rule, dims);
+            last_update = std::time::Instant::now();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+/// Refreshes `dims` from the terminal, returning `true` if it changed so the
+/// caller can re-fit its board. This is how a SIGWINCH resize is picked up.
+fn handle_resize(dims: &mut Dimensions) -> bool {
+    let current = Dimensions::from_terminal();
+    if current != *dims {
+        *dims = current;
+        true
+    } else {
+        false
     }
 }
 
-fn initialize_board() -> Vec<Vec<Cell>> {
+/// Rebuilds a dense board at new dimensions, preserving the state of every
+/// cell that still falls inside the resized grid.
+fn refit_board(old: Vec<Vec<Cell>>, dims: Dimensions) -> Vec<Vec<Cell>> {
     let mut board: Vec<Vec<Cell>> = Vec::new();
-    for i in 0..WINDOW_WIDTH {
+    for i in 0..dims.width {
         let mut row: Vec<Cell> = Vec::new();
-        for j in 0..WINDOW_HEIGHT {
-            row.push(generate_random_cell(i, j));
+        for j in 0..dims.height {
+            let cell = old
+                .get(i as usize)
+                .and_then(|col| col.get(j as usize))
+                .cloned()
+                .unwrap_or(Cell {
+                    x: i,
+                    y: j,
+                    state: CellState::Dead,
+                    age: 0,
+                });
+            row.push(cell);
         }
         board.push(row);
     }
     board
 }
 
-fn update_board(board: &mut Vec<Vec<Cell>>) {
-    let board_clone = board.clone();
+/// Sets every cell on the board to dead.
+fn clear_board(board: &mut [Vec<Cell>]) {
     for row in board.iter_mut() {
         for cell in row.iter_mut() {
-            let num_neighbors = count_neighbors(cell, &board_clone);
+            cell.state = CellState::Dead;
+            cell.age = 0;
+        }
+    }
+}
 
-            cell.state = match (cell.state, num_neighbors) {
-                (CellState::Alive, 2..=3) => CellState::Alive,
-                (CellState::Dead, 3) => CellState::Alive,
-                _ => CellState::Dead,
+/// Highlights the editor cursor by drawing a reversed cell at its position.
+fn draw_cursor(x: u16, y: u16, dims: Dimensions) {
+    print!(
+        "{}{}{}{}{}",
+        cursor::Goto(x + 1, y + 1),
+        color::Bg(color::White),
+        ALIVE_SYMBOL,
+        color::Bg(color::Reset),
+        cursor::Goto(dims.width + 1, dims.height + 1)
+    );
+}
+
+/// Interactive run loop for the sparse backend. Auto-advances on the frame
+/// timer while running; space toggles pause, `n`/`b` step forward/back
+/// through history while paused, `r` resets to the initial state and `q`
+/// quits. Unlike `run_dense`, there is no cursor-painting editor and no
+/// age-color rendering: the `Backend` trait carries neither per-cell age
+/// nor a paint hook, so live cells are drawn as flat `ALIVE_SYMBOL`.
+fn run_sparse(board: &mut SparseBoard, boundary: BoundaryMode, rule: &Rule, mut dims: Dimensions) {
+    let _raw = std::io::stdout().into_raw_mode().unwrap();
+    let mut keys = termion::async_stdin().keys();
+    let mut paused = false;
+    let mut last_update = std::time::Instant::now();
+
+    loop {
+        handle_resize(&mut dims);
+        render_live(board, dims);
+
+        while let Some(Ok(key)) = keys.next() {
+            match key {
+                event::Key::Char('q') => return,
+                event::Key::Char(' ') => paused = !paused,
+                event::Key::Char('n') if paused => board.tick(boundary, rule, dims),
+                event::Key::Char('r') => board.reset(),
+                event::Key::Char('b') if paused => board.step_back(),
+                _ => {}
+            }
+        }
+
+        if !paused && last_update.elapsed().as_millis() >= FRAME_DURATION_MS {
+            board.tick(boundary, rule, dims);
+            last_update = std::time::Instant::now();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+/// Renders just the live cells of a backend onto a cleared screen.
+fn render_live<B: Backend>(board: &B, dims: Dimensions) {
+    print!("{}", clear::All);
+    for (x, y) in board.live_cells(dims) {
+        print_char(x, y, ALIVE_SYMBOL, dims);
+    }
+    std::io::stdout().flush().unwrap();
+}
+
+/// Collects the live-cell coordinates of a dense board into a sparse set.
+fn live_set(board: &[Vec<Cell>]) -> HashSet<(isize, isize)> {
+    let mut set = HashSet::new();
+    for row in board.iter() {
+        for cell in row.iter() {
+            if cell.state == CellState::Alive {
+                set.insert((cell.x as isize, cell.y as isize));
+            }
+        }
+    }
+    set
+}
+
+/// Returns the backend selected by the `--backend <dense|sparse>` CLI flag,
+/// defaulting to the dense grid.
+fn parse_backend_arg() -> Result<BackendKind, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--backend" {
+            return match args.next().as_deref() {
+                Some("dense") => Ok(BackendKind::Dense),
+                Some("sparse") => Ok(BackendKind::Sparse),
+                Some(other) => Err(format!("unknown backend '{}'", other)),
+                None => Err("missing value for --backend".to_string()),
             };
         }
     }
+    Ok(BackendKind::Dense)
+}
+
+/// Returns the rule selected by the `--rule <B../S..>` CLI flag, defaulting
+/// to Conway's Life.
+fn parse_rule_arg() -> Result<Rule, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--rule" {
+            return match args.next() {
+                Some(s) => Rule::parse(&s),
+                None => Err("missing value for --rule".to_string()),
+            };
+        }
+    }
+    Ok(Rule::conway())
+}
+
+/// Returns the boundary mode selected by the `--boundary <bounded|toroidal>`
+/// CLI flag, defaulting to `Bounded`.
+fn parse_boundary_arg() -> Result<BoundaryMode, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--boundary" {
+            return match args.next().as_deref() {
+                Some("bounded") => Ok(BoundaryMode::Bounded),
+                Some("toroidal") => Ok(BoundaryMode::Toroidal),
+                Some(other) => Err(format!("unknown boundary mode '{}'", other)),
+                None => Err("missing value for --boundary".to_string()),
+            };
+        }
+    }
+    Ok(BoundaryMode::Bounded)
+}
+
+/// Returns the value of the `--pattern <path>` CLI flag if present.
+fn parse_pattern_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--pattern" {
+            return args.next();
+        }
+    }
+    None
+}
+
+fn initialize_board(pattern: Option<&Pattern>, dims: Dimensions) -> Vec<Vec<Cell>> {
+    let mut board: Vec<Vec<Cell>> = Vec::new();
+    for i in 0..dims.width {
+        let mut row: Vec<Cell> = Vec::new();
+        for j in 0..dims.height {
+            match pattern {
+                Some(_) => row.push(Cell {
+                    x: i,
+                    y: j,
+                    state: CellState::Dead,
+                    age: 0,
+                }),
+                None => row.push(generate_random_cell(i, j)),
+            }
+        }
+        board.push(row);
+    }
+    if let Some(p) = pattern {
+        place_pattern(&mut board, p, dims);
+    }
+    board
+}
+
+/// Stamps a pattern onto an otherwise dead board, centered horizontally
+/// and vertically.
+fn place_pattern(board: &mut [Vec<Cell>], pattern: &Pattern, dims: Dimensions) {
+    let offset_x = (dims.width as usize - pattern.width) / 2;
+    let offset_y = (dims.height as usize - pattern.height) / 2;
+    for &(col, row) in pattern.alive.iter() {
+        board[offset_x + col][offset_y + row].state = CellState::Alive;
+    }
+}
+
+/// Loads a pattern file, dispatching on extension: `.rle` is parsed as
+/// run-length encoded, everything else as the plaintext `.cells` format.
+fn load_pattern(path: &str, dims: Dimensions) -> Result<Pattern, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let pattern = if path.to_lowercase().ends_with(".rle") {
+        parse_rle(&text)?
+    } else {
+        parse_plaintext(&text)?
+    };
+    if pattern.width > dims.width as usize || pattern.height > dims.height as usize {
+        return Err(format!(
+            "pattern is {}x{} but the board is only {}x{}",
+            pattern.width, pattern.height, dims.width, dims.height
+        ));
+    }
+    Ok(pattern)
+}
+
+/// Parses the plaintext `.cells` format: `!`-prefixed comment lines,
+/// `.` for dead cells and `O`/`*` for live ones.
+fn parse_plaintext(text: &str) -> Result<Pattern, String> {
+    let mut alive: Vec<(usize, usize)> = Vec::new();
+    let mut width = 0;
+    let mut row = 0;
+    for line in text.lines() {
+        if line.starts_with('!') {
+            continue;
+        }
+        for (col, ch) in line.chars().enumerate() {
+            if ch == 'O' || ch == '*' {
+                alive.push((col, row));
+            }
+        }
+        width = width.max(line.chars().count());
+        row += 1;
+    }
+    if alive.is_empty() {
+        return Err("no live cells found".to_string());
+    }
+    Ok(Pattern {
+        width,
+        height: row,
+        alive,
+    })
 }
 
-fn count_neighbors(cell: &mut Cell, board: &[Vec<Cell>]) -> i32 {
+/// Parses the run-length-encoded `.rle` format. The header `x = m, y = n`
+/// gives the bounding box (an optional `rule = ...` field is ignored here);
+/// the body uses `o` for live, `b` for dead, `$` for end of row and `!` for
+/// end of pattern, each optionally prefixed by a run count that defaults to 1.
+fn parse_rle(text: &str) -> Result<Pattern, String> {
+    let mut width = 0usize;
+    let mut height = 0usize;
+    let mut body = String::new();
+    let mut seen_header = false;
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        if !seen_header && line.trim_start().starts_with('x') {
+            for field in line.split(',') {
+                let mut parts = field.splitn(2, '=');
+                let key = parts.next().unwrap_or("").trim();
+                let value = parts.next().unwrap_or("").trim();
+                match key {
+                    "x" => width = value.parse().map_err(|_| "invalid x in header")?,
+                    "y" => height = value.parse().map_err(|_| "invalid y in header")?,
+                    _ => {} // the optional `rule` field is not used here
+                }
+            }
+            seen_header = true;
+            continue;
+        }
+        body.push_str(line.trim());
+    }
+    if !seen_header {
+        return Err("missing `x = .., y = ..` header".to_string());
+    }
+
+    let mut alive: Vec<(usize, usize)> = Vec::new();
+    let mut count: usize = 0;
+    let mut col = 0usize;
+    let mut row = 0usize;
+    for ch in body.chars() {
+        match ch {
+            '0'..='9' => count = count * 10 + ch.to_digit(10).unwrap() as usize,
+            'b' | 'o' | '$' => {
+                let run = if count == 0 { 1 } else { count };
+                match ch {
+                    'o' => {
+                        for k in 0..run {
+                            let (c, r) = (col + k, row);
+                            if c >= width || r >= height {
+                                return Err(format!(
+                                    "pattern body runs past its {}x{} header at ({}, {})",
+                                    width, height, c, r
+                                ));
+                            }
+                            alive.push((c, r));
+                        }
+                        col += run;
+                    }
+                    'b' => col += run,
+                    '$' => {
+                        row += run;
+                        col = 0;
+                    }
+                    _ => unreachable!(),
+                }
+                count = 0;
+            }
+            '!' => break,
+            c if c.is_whitespace() => continue,
+            other => return Err(format!("unexpected token '{}' in body", other)),
+        }
+    }
+    Ok(Pattern {
+        width,
+        height,
+        alive,
+    })
+}
+
+fn update_board(board: &mut Vec<Vec<Cell>>, boundary: BoundaryMode, rule: &Rule, dims: Dimensions) {
+    let board_clone = board.clone();
+    for row in board.iter_mut() {
+        for cell in row.iter_mut() {
+            let num_neighbors = count_neighbors(cell, &board_clone, boundary, dims);
+
+            let alive = cell.state == CellState::Alive;
+            if alive && rule.survives.contains(&num_neighbors) {
+                cell.age = cell.age.saturating_add(1);
+            } else if !alive && rule.born.contains(&num_neighbors) {
+                cell.state = CellState::Alive;
+                cell.age = 0;
+            } else {
+                cell.state = CellState::Dead;
+                cell.age = 0;
+            }
+        }
+    }
+}
+
+fn count_neighbors(
+    cell: &mut Cell,
+    board: &[Vec<Cell>],
+    boundary: BoundaryMode,
+    dims: Dimensions,
+) -> i32 {
     let mut num_neighbors = 0;
     for i in -1..=1 {
         for j in -1..=1 {
             if i == 0 && j == 0 {
                 continue;
             }
-            let x = cell.x as i16 + i;
-            let y = cell.y as i16 + j;
-            if x < 0 || y < 0 || x >= WINDOW_WIDTH as i16 || y >= WINDOW_HEIGHT as i16 {
-                continue;
-            }
+            let (x, y) = match boundary {
+                BoundaryMode::Bounded => {
+                    let x = cell.x as i16 + i;
+                    let y = cell.y as i16 + j;
+                    if x < 0 || y < 0 || x >= dims.width as i16 || y >= dims.height as i16 {
+                        continue;
+                    }
+                    (x, y)
+                }
+                BoundaryMode::Toroidal => {
+                    let x = (cell.x as i16 + i + dims.width as i16) % dims.width as i16;
+                    let y = (cell.y as i16 + j + dims.height as i16) % dims.height as i16;
+                    (x, y)
+                }
+            };
             if board[x as usize][y as usize].state == CellState::Alive {
                 num_neighbors += 1;
             }
@@ -87,52 +738,402 @@ fn count_neighbors(cell: &mut Cell, board: &[Vec<Cell>]) -> i32 {
     num_neighbors
 }
 
-fn render_board(board: &[Vec<Cell>]) {
+fn render_board(board: &[Vec<Cell>], dims: Dimensions) {
     for row in board.iter() {
         for cell in row.iter() {
-            let c = match cell.state {
-                CellState::Alive => ALIVE_SYMBOL,
-                CellState::Dead => DEAD_SYMBOL,
-            };
-            print_char(cell.x, cell.y, c);
+            match cell.state {
+                CellState::Alive => print_aged_char(cell.x, cell.y, cell.age, dims),
+                CellState::Dead => print_char(cell.x, cell.y, DEAD_SYMBOL, dims),
+            }
         }
     }
 }
 
-fn print_char(x: u16, y: u16, c: char) {
+fn print_char(x: u16, y: u16, c: char, dims: Dimensions) {
     print!(
         "{}{}{}",
         cursor::Goto(x + 1, y + 1),
         c,
-        cursor::Goto(WINDOW_WIDTH + 1, WINDOW_HEIGHT + 1)
+        cursor::Goto(dims.width + 1, dims.height + 1)
     );
 }
 
+/// Draws a live cell colored by its age: bright white when newborn, cooling
+/// toward blue the longer it has survived.
+fn print_aged_char(x: u16, y: u16, age: u32, dims: Dimensions) {
+    print!(
+        "{}{}{}{}{}",
+        cursor::Goto(x + 1, y + 1),
+        color::Fg(age_color(age)),
+        ALIVE_SYMBOL,
+        color::Fg(color::Reset),
+        cursor::Goto(dims.width + 1, dims.height + 1)
+    );
+}
+
+/// Maps a cell age to a point on a white-to-blue foreground ramp, saturating
+/// after a handful of generations so stable structures settle on a color.
+fn age_color(age: u32) -> color::Rgb {
+    const RAMP_LEN: f32 = 16.0;
+    let t = (age.min(RAMP_LEN as u32) as f32) / RAMP_LEN;
+    let r = (255.0 * (1.0 - t)) as u8;
+    let g = (255.0 * (1.0 - t * 0.5)) as u8;
+    color::Rgb(r, g, 255)
+}
+
 fn generate_random_cell(x: u16, y: u16) -> Cell {
     let state = if rand::random() && rand::random() {
         CellState::Alive
     } else {
         CellState::Dead
     };
-    Cell { x, y, state }
+    Cell {
+        x,
+        y,
+        state,
+        age: 0,
+    }
 }
 
-fn wait_for_next_frame(last_update: std::time::Instant) {
-    let now = std::time::Instant::now();
-    let elapsed = now.duration_since(last_update);
-    if elapsed.as_millis() < FRAME_DURATION_MS {
-        std::thread::sleep(std::time::Duration::from_millis(
-            (FRAME_DURATION_MS - elapsed.as_millis()) as u64,
-        ));
+#[cfg(test)]
+mod pattern_tests {
+    use super::*;
+
+    #[test]
+    fn plaintext_marks_live_cells_and_skips_comments() {
+        let pattern = parse_plaintext("!a glider\n.O.\n..O\nOOO\n").unwrap();
+        assert_eq!(pattern.width, 3);
+        assert_eq!(pattern.height, 3);
+        assert!(pattern.alive.contains(&(1, 0)));
+        assert!(pattern.alive.contains(&(0, 2)));
+        assert_eq!(pattern.alive.len(), 5);
+    }
+
+    #[test]
+    fn plaintext_accepts_star_as_alive() {
+        let pattern = parse_plaintext("*.*\n").unwrap();
+        assert_eq!(pattern.alive, vec![(0, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn plaintext_empty_is_an_error() {
+        assert!(parse_plaintext("!only a comment\n...\n").is_err());
+    }
+
+    #[test]
+    fn rle_defaults_missing_run_count_to_one() {
+        let pattern = parse_rle("x = 3, y = 1\nobo!\n").unwrap();
+        assert_eq!((pattern.width, pattern.height), (3, 1));
+        assert_eq!(pattern.alive, vec![(0, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn rle_decodes_runs_and_rows() {
+        let pattern = parse_rle("x = 3, y = 2\n3o$bo!\n").unwrap();
+        assert!(pattern.alive.contains(&(0, 0)));
+        assert!(pattern.alive.contains(&(2, 0)));
+        assert!(pattern.alive.contains(&(1, 1)));
+        assert_eq!(pattern.alive.len(), 4);
+    }
+
+    #[test]
+    fn rle_ignores_optional_rule_field() {
+        let pattern = parse_rle("x = 1, y = 1, rule = B3/S23\no!\n").unwrap();
+        assert_eq!(pattern.alive, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn rle_rejects_missing_header() {
+        assert!(parse_rle("bo!\n").is_err());
+    }
+
+    #[test]
+    fn rle_rejects_body_past_header() {
+        // Header claims a 1x1 box but the body places a cell at column 3.
+        assert!(parse_rle("x = 1, y = 1\n3bo!\n").is_err());
     }
 }
 
-fn check_terminal_size() {
-    let (width, height) = termion::terminal_size().unwrap();
-    if width < WINDOW_WIDTH || height < WINDOW_HEIGHT {
-        panic!(
-            "Terminal size too small. Please resize terminal to at least {} by {}",
-            WINDOW_WIDTH, WINDOW_HEIGHT
+#[cfg(test)]
+mod count_neighbors_tests {
+    use super::*;
+
+    fn board_with_alive_at(width: u16, height: u16, alive: &[(u16, u16)]) -> Vec<Vec<Cell>> {
+        let mut board = Vec::new();
+        for i in 0..width {
+            let mut row = Vec::new();
+            for j in 0..height {
+                row.push(Cell {
+                    x: i,
+                    y: j,
+                    state: if alive.contains(&(i, j)) {
+                        CellState::Alive
+                    } else {
+                        CellState::Dead
+                    },
+                    age: 0,
+                });
+            }
+            board.push(row);
+        }
+        board
+    }
+
+    #[test]
+    fn bounded_does_not_wrap_across_the_right_edge() {
+        let dims = Dimensions {
+            width: 4,
+            height: 4,
+        };
+        let board = board_with_alive_at(4, 4, &[(0, 1)]);
+        let mut edge_cell = board[3][1].clone();
+        let n = count_neighbors(&mut edge_cell, &board, BoundaryMode::Bounded, dims);
+        assert_eq!(n, 0);
+    }
+
+    #[test]
+    fn toroidal_wraps_a_cell_off_the_right_edge_back_onto_the_left() {
+        // The acceptance criterion from chunk0-2: a cell leaving the right
+        // edge re-enters on the left. A live cell at (0, 1) should count as
+        // a neighbor of (width - 1, 1) only in Toroidal mode.
+        let dims = Dimensions {
+            width: 4,
+            height: 4,
+        };
+        let board = board_with_alive_at(4, 4, &[(0, 1)]);
+        let mut edge_cell = board[3][1].clone();
+        let n = count_neighbors(&mut edge_cell, &board, BoundaryMode::Toroidal, dims);
+        assert_eq!(n, 1);
+    }
+}
+
+#[cfg(test)]
+mod sparse_neighbors_tests {
+    use super::*;
+
+    #[test]
+    fn bounded_drops_out_of_range_neighbors() {
+        let neighbors = sparse_neighbors(
+            0,
+            0,
+            BoundaryMode::Bounded,
+            Dimensions {
+                width: 5,
+                height: 5,
+            },
+        );
+        assert_eq!(neighbors.len(), 3);
+        assert!(neighbors.contains(&(1, 0)));
+        assert!(neighbors.contains(&(0, 1)));
+        assert!(neighbors.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn toroidal_wraps_corners_around_to_the_opposite_edge() {
+        let neighbors = sparse_neighbors(
+            0,
+            0,
+            BoundaryMode::Toroidal,
+            Dimensions {
+                width: 5,
+                height: 5,
+            },
         );
+        assert_eq!(neighbors.len(), 8);
+        assert!(neighbors.contains(&(4, 4)));
+        assert!(neighbors.contains(&(4, 0)));
+        assert!(neighbors.contains(&(0, 4)));
+    }
+
+    #[test]
+    fn toroidal_wraps_far_edge_back_to_zero() {
+        let neighbors = sparse_neighbors(
+            4,
+            4,
+            BoundaryMode::Toroidal,
+            Dimensions {
+                width: 5,
+                height: 5,
+            },
+        );
+        assert!(neighbors.contains(&(0, 0)));
+        assert!(neighbors.contains(&(0, 4)));
+        assert!(neighbors.contains(&(4, 0)));
+    }
+}
+
+#[cfg(test)]
+mod sparse_board_tests {
+    use super::*;
+
+    fn dims(width: u16, height: u16) -> Dimensions {
+        Dimensions { width, height }
+    }
+
+    #[test]
+    fn isolated_cell_survives_under_rule_with_zero_neighbors() {
+        // B3/S012345678 ("Life without Death"): every live cell survives
+        // regardless of neighbor count, so a lone live cell with zero live
+        // neighbors must still be alive after a tick.
+        let rule = Rule::parse("B3/S012345678").unwrap();
+        let mut board = SparseBoard::new(HashSet::from([(5, 5)]));
+        board.tick(BoundaryMode::Bounded, &rule, dims(10, 10));
+        assert_eq!(board.live, HashSet::from([(5, 5)]));
+    }
+
+    #[test]
+    fn blinker_oscillates_under_conway() {
+        let rule = Rule::conway();
+        let mut board = SparseBoard::new(HashSet::from([(1, 2), (2, 2), (3, 2)]));
+        board.tick(BoundaryMode::Bounded, &rule, dims(5, 5));
+        assert_eq!(board.live, HashSet::from([(2, 1), (2, 2), (2, 3)]));
+        board.tick(BoundaryMode::Bounded, &rule, dims(5, 5));
+        assert_eq!(board.live, HashSet::from([(1, 2), (2, 2), (3, 2)]));
+    }
+
+    #[test]
+    fn step_back_rewinds_to_the_prior_generation() {
+        let rule = Rule::conway();
+        let initial = HashSet::from([(1, 2), (2, 2), (3, 2)]);
+        let mut board = SparseBoard::new(initial.clone());
+        board.tick(BoundaryMode::Bounded, &rule, dims(5, 5));
+        assert_ne!(board.live, initial);
+        board.step_back();
+        assert_eq!(board.live, initial);
+    }
+
+    #[test]
+    fn step_back_on_untouched_board_is_a_no_op() {
+        let initial = HashSet::from([(1, 2)]);
+        let mut board = SparseBoard::new(initial.clone());
+        board.step_back();
+        assert_eq!(board.live, initial);
+    }
+
+    #[test]
+    fn reset_restores_initial_state_and_clears_history() {
+        let rule = Rule::conway();
+        let initial = HashSet::from([(1, 2), (2, 2), (3, 2)]);
+        let mut board = SparseBoard::new(initial.clone());
+        board.tick(BoundaryMode::Bounded, &rule, dims(5, 5));
+        board.tick(BoundaryMode::Bounded, &rule, dims(5, 5));
+        board.reset();
+        assert_eq!(board.live, initial);
+        assert!(board.history.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod refit_board_tests {
+    use super::*;
+
+    fn board_with_alive_at(width: u16, height: u16, alive: &[(u16, u16)]) -> Vec<Vec<Cell>> {
+        let mut board = Vec::new();
+        for i in 0..width {
+            let mut row = Vec::new();
+            for j in 0..height {
+                row.push(Cell {
+                    x: i,
+                    y: j,
+                    state: if alive.contains(&(i, j)) {
+                        CellState::Alive
+                    } else {
+                        CellState::Dead
+                    },
+                    age: 0,
+                });
+            }
+            board.push(row);
+        }
+        board
+    }
+
+    #[test]
+    fn growing_the_board_preserves_existing_cells_and_fills_dead() {
+        let board = board_with_alive_at(3, 3, &[(1, 1)]);
+        let refit = refit_board(
+            board,
+            Dimensions {
+                width: 5,
+                height: 5,
+            },
+        );
+        assert_eq!(refit.len(), 5);
+        assert_eq!(refit[0].len(), 5);
+        assert_eq!(refit[1][1].state, CellState::Alive);
+        assert_eq!(refit[4][4].state, CellState::Dead);
+    }
+
+    #[test]
+    fn shrinking_the_board_drops_cells_outside_the_new_bounds() {
+        let board = board_with_alive_at(5, 5, &[(4, 4)]);
+        let refit = refit_board(
+            board,
+            Dimensions {
+                width: 3,
+                height: 3,
+            },
+        );
+        assert_eq!(refit.len(), 3);
+        assert_eq!(refit[0].len(), 3);
+    }
+}
+
+#[cfg(test)]
+mod age_color_tests {
+    use super::*;
+
+    #[test]
+    fn newborn_cell_is_bright_white() {
+        assert_eq!(age_color(0), color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn gradient_cools_toward_blue_as_age_increases() {
+        let young = age_color(1);
+        let old = age_color(8);
+        assert!(old.0 < young.0);
+        assert!(old.1 < young.1);
+    }
+
+    #[test]
+    fn gradient_saturates_past_the_ramp_length() {
+        assert_eq!(age_color(16), age_color(1000));
+    }
+}
+
+#[cfg(test)]
+mod rule_tests {
+    use super::*;
+
+    #[test]
+    fn parses_conway() {
+        let rule = Rule::parse("B3/S23").unwrap();
+        assert_eq!(rule.born, HashSet::from([3]));
+        assert_eq!(rule.survives, HashSet::from([2, 3]));
+    }
+
+    #[test]
+    fn empty_survival_side_is_allowed() {
+        let rule = Rule::parse("B2/S").unwrap();
+        assert_eq!(rule.born, HashSet::from([2]));
+        assert!(rule.survives.is_empty());
+    }
+
+    #[test]
+    fn lowercase_prefixes_are_accepted() {
+        let rule = Rule::parse("b36/s23").unwrap();
+        assert_eq!(rule.born, HashSet::from([3, 6]));
+        assert_eq!(rule.survives, HashSet::from([2, 3]));
+    }
+
+    #[test]
+    fn missing_prefix_is_an_error() {
+        assert!(Rule::parse("3/S23").is_err());
+    }
+
+    #[test]
+    fn non_digit_count_is_an_error() {
+        assert!(Rule::parse("B3/Sxy").is_err());
     }
 }